@@ -1,11 +1,13 @@
+mod lyapunov;
 mod ode;
 mod systems;
 
-use nalgebra::{Vector2, Vector3};
+use nalgebra::{SVector, Vector1, Vector2, Vector3};
 use wasm_bindgen::prelude::*;
 
-use crate::ode::RK4Integrator;
-use crate::systems::{DampedPendulum, Lorenz, Rossler, VanDerPol};
+use crate::lyapunov::lyapunov_spectrum;
+use crate::ode::{RK4Integrator, RKF45Integrator, RosenbrockIntegrator, VelocityVerletIntegrator};
+use crate::systems::{DampedPendulum, DynamicalSystem, Lorenz, Rossler, VanDerPol};
 
 /// Solve the Lorenz system.
 ///
@@ -49,7 +51,10 @@ pub fn solve_van_der_pol(
 
 /// Solve the damped pendulum.
 ///
-/// Returns a flat array of [θ0, ω0, θ1, ω1, ...] values.
+/// Returns a flat array of [θ0, ω0, θ1, ω1, ...] values. When `gamma == 0`
+/// the pendulum is conservative, so this routes through the symplectic
+/// `VelocityVerletIntegrator` instead of RK4 to keep total energy bounded
+/// over long integrations rather than slowly dissipating it.
 #[wasm_bindgen]
 pub fn solve_damped_pendulum(
     gamma: f64,
@@ -60,6 +65,15 @@ pub fn solve_damped_pendulum(
     steps: usize,
 ) -> Vec<f64> {
     let pendulum = DampedPendulum::new(gamma, omega0);
+
+    if pendulum.is_conservative() {
+        let integrator = VelocityVerletIntegrator::<1>::new(dt);
+        let q0 = Vector1::new(theta0);
+        let v0 = Vector1::new(omega_init);
+        let a = |q: &Vector1<f64>| Vector1::new(pendulum.acceleration(q[0]));
+        return integrator.integrate(&a, q0, v0, steps);
+    }
+
     let integrator = RK4Integrator::<2>::new(dt);
     let initial = Vector2::new(theta0, omega_init);
 
@@ -87,6 +101,415 @@ pub fn solve_rossler(
     integrator.integrate(&|t, y| rossler.derivative(t, y), initial, steps)
 }
 
+/// Integrate any `DynamicalSystem` with the implicit `RosenbrockIntegrator`,
+/// mirroring `integrate_system`'s trait-based dispatch for the stiff path.
+fn integrate_system_stiff<S, const N: usize>(
+    system: &S,
+    initial: SVector<f64, N>,
+    dt: f64,
+    steps: usize,
+) -> Vec<f64>
+where
+    S: DynamicalSystem<N>,
+{
+    let integrator = RosenbrockIntegrator::<N>::new(dt);
+    integrator.integrate(&|t, y| system.derivative(t, y), initial, steps)
+}
+
+/// Solve a named dynamical system with the implicit `RosenbrockIntegrator`
+/// instead of RK4, for stiff regimes (e.g. the damped pendulum with large
+/// `gamma`, or Van der Pol with large `mu`) where explicit stepping needs
+/// impractically small `dt`. Lets callers pick explicit vs. implicit per
+/// problem while reusing the same `DynamicalSystem` impls as `solve`.
+///
+/// `params` and `y0` are validated against the system's expected parameter
+/// and state-space dimension before integrating.
+fn solve_stiff_impl(
+    system_name: &str,
+    params: &[f64],
+    y0: &[f64],
+    dt: f64,
+    steps: usize,
+) -> Result<Vec<f64>, String> {
+    match system_name {
+        "lorenz" => {
+            expect_len("lorenz", "params", params.len(), 3)?;
+            expect_len("lorenz", "initial state values", y0.len(), 3)?;
+            let system = Lorenz::new(params[0], params[1], params[2]);
+            let initial = Vector3::new(y0[0], y0[1], y0[2]);
+            Ok(integrate_system_stiff(&system, initial, dt, steps))
+        }
+        "rossler" => {
+            expect_len("rossler", "params", params.len(), 3)?;
+            expect_len("rossler", "initial state values", y0.len(), 3)?;
+            let system = Rossler::new(params[0], params[1], params[2]);
+            let initial = Vector3::new(y0[0], y0[1], y0[2]);
+            Ok(integrate_system_stiff(&system, initial, dt, steps))
+        }
+        "van_der_pol" => {
+            expect_len("van_der_pol", "params", params.len(), 1)?;
+            expect_len("van_der_pol", "initial state values", y0.len(), 2)?;
+            let system = VanDerPol::new(params[0]);
+            let initial = Vector2::new(y0[0], y0[1]);
+            Ok(integrate_system_stiff(&system, initial, dt, steps))
+        }
+        "damped_pendulum" => {
+            expect_len("damped_pendulum", "params", params.len(), 2)?;
+            expect_len("damped_pendulum", "initial state values", y0.len(), 2)?;
+            let system = DampedPendulum::new(params[0], params[1]);
+            let initial = Vector2::new(y0[0], y0[1]);
+            Ok(integrate_system_stiff(&system, initial, dt, steps))
+        }
+        other => Err(format!("unknown system: {other}")),
+    }
+}
+
+/// Thin wasm-exported wrapper around `solve_stiff_impl`; see `solve`'s
+/// wrapper for why the `JsValue` conversion has to happen at this boundary
+/// rather than inside the natively-tested logic.
+#[wasm_bindgen]
+pub fn solve_stiff(
+    system_name: &str,
+    params: &[f64],
+    y0: &[f64],
+    dt: f64,
+    steps: usize,
+) -> Result<Vec<f64>, JsValue> {
+    solve_stiff_impl(system_name, params, y0, dt, steps).map_err(|e| JsValue::from_str(&e))
+}
+
+/// Integrate any `DynamicalSystem` with `RKF45Integrator`, flattening the
+/// irregularly-spaced `(times, states)` pairs into `[t0, state0..., t1,
+/// state1..., ...]` groups of `1 + N` values, since adaptive stepping can't
+/// return a fixed-cadence `Vec<f64>` the way `integrate_system` does.
+fn integrate_system_adaptive<S, const N: usize>(
+    system: &S,
+    initial: SVector<f64, N>,
+    tol: f64,
+    dt_min: f64,
+    dt_max: f64,
+    t0: f64,
+    t1: f64,
+) -> Vec<f64>
+where
+    S: DynamicalSystem<N>,
+{
+    let integrator = RKF45Integrator::<N>::new(tol, dt_min, dt_max);
+    let (times, states) = integrator.integrate(&|t, y| system.derivative(t, y), initial, t0, t1);
+
+    let mut result = Vec::with_capacity(times.len() * (N + 1));
+    for (t, state) in times.iter().zip(states.iter()) {
+        result.push(*t);
+        for i in 0..N {
+            result.push(state[i]);
+        }
+    }
+    result
+}
+
+/// Solve a named dynamical system with adaptive RKF45 stepping instead of a
+/// fixed `dt`, letting the step size shrink automatically in fast-changing
+/// regions and grow where the dynamics are smooth.
+///
+/// `params` and `y0` are validated against the system's expected parameter
+/// and state-space dimension before integrating.
+///
+/// Returns a flat array of `[t0, state0..., t1, state1..., ...]` groups,
+/// `1 + dim` values each, since accepted steps are not evenly spaced.
+fn solve_adaptive_impl(
+    system_name: &str,
+    params: &[f64],
+    y0: &[f64],
+    tol: f64,
+    dt_min: f64,
+    dt_max: f64,
+    t0: f64,
+    t1: f64,
+) -> Result<Vec<f64>, String> {
+    match system_name {
+        "lorenz" => {
+            expect_len("lorenz", "params", params.len(), 3)?;
+            expect_len("lorenz", "initial state values", y0.len(), 3)?;
+            let system = Lorenz::new(params[0], params[1], params[2]);
+            let initial = Vector3::new(y0[0], y0[1], y0[2]);
+            Ok(integrate_system_adaptive(
+                &system, initial, tol, dt_min, dt_max, t0, t1,
+            ))
+        }
+        "rossler" => {
+            expect_len("rossler", "params", params.len(), 3)?;
+            expect_len("rossler", "initial state values", y0.len(), 3)?;
+            let system = Rossler::new(params[0], params[1], params[2]);
+            let initial = Vector3::new(y0[0], y0[1], y0[2]);
+            Ok(integrate_system_adaptive(
+                &system, initial, tol, dt_min, dt_max, t0, t1,
+            ))
+        }
+        "van_der_pol" => {
+            expect_len("van_der_pol", "params", params.len(), 1)?;
+            expect_len("van_der_pol", "initial state values", y0.len(), 2)?;
+            let system = VanDerPol::new(params[0]);
+            let initial = Vector2::new(y0[0], y0[1]);
+            Ok(integrate_system_adaptive(
+                &system, initial, tol, dt_min, dt_max, t0, t1,
+            ))
+        }
+        "damped_pendulum" => {
+            expect_len("damped_pendulum", "params", params.len(), 2)?;
+            expect_len("damped_pendulum", "initial state values", y0.len(), 2)?;
+            let system = DampedPendulum::new(params[0], params[1]);
+            let initial = Vector2::new(y0[0], y0[1]);
+            Ok(integrate_system_adaptive(
+                &system, initial, tol, dt_min, dt_max, t0, t1,
+            ))
+        }
+        other => Err(format!("unknown system: {other}")),
+    }
+}
+
+/// Thin wasm-exported wrapper around `solve_adaptive_impl`; see `solve`'s
+/// wrapper for why the `JsValue` conversion has to happen at this boundary.
+#[wasm_bindgen]
+pub fn solve_adaptive(
+    system_name: &str,
+    params: &[f64],
+    y0: &[f64],
+    tol: f64,
+    dt_min: f64,
+    dt_max: f64,
+    t0: f64,
+    t1: f64,
+) -> Result<Vec<f64>, JsValue> {
+    solve_adaptive_impl(system_name, params, y0, tol, dt_min, dt_max, t0, t1)
+        .map_err(|e| JsValue::from_str(&e))
+}
+
+/// Integrate any `DynamicalSystem` with RK4 at its own internal `dt`, then
+/// resample at the caller-requested `tspan` via `RK4Integrator::solve_at`'s
+/// cubic Hermite interpolation. Lets callers request evenly spaced frames
+/// (or any other cadence) regardless of the integrator's own step size.
+fn sample_system_at<S, const N: usize>(
+    system: &S,
+    initial: SVector<f64, N>,
+    dt: f64,
+    tspan: &[f64],
+) -> Vec<f64>
+where
+    S: DynamicalSystem<N>,
+{
+    let integrator = RK4Integrator::<N>::new(dt);
+    integrator.solve_at(&|t, y| system.derivative(t, y), initial, tspan)
+}
+
+/// Solve a named dynamical system and resample it at specific times instead
+/// of returning one frame per internal integration step.
+///
+/// `params` and `y0` are validated against the system's expected parameter
+/// and state-space dimension before integrating; `tspan` is the list of
+/// times (not necessarily evenly spaced) to sample the trajectory at.
+///
+/// Returns a flat array of state values, one group of `dim` per `tspan` entry.
+fn solve_frames_impl(
+    system_name: &str,
+    params: &[f64],
+    y0: &[f64],
+    dt: f64,
+    tspan: &[f64],
+) -> Result<Vec<f64>, String> {
+    match system_name {
+        "lorenz" => {
+            expect_len("lorenz", "params", params.len(), 3)?;
+            expect_len("lorenz", "initial state values", y0.len(), 3)?;
+            let system = Lorenz::new(params[0], params[1], params[2]);
+            let initial = Vector3::new(y0[0], y0[1], y0[2]);
+            Ok(sample_system_at(&system, initial, dt, tspan))
+        }
+        "rossler" => {
+            expect_len("rossler", "params", params.len(), 3)?;
+            expect_len("rossler", "initial state values", y0.len(), 3)?;
+            let system = Rossler::new(params[0], params[1], params[2]);
+            let initial = Vector3::new(y0[0], y0[1], y0[2]);
+            Ok(sample_system_at(&system, initial, dt, tspan))
+        }
+        "van_der_pol" => {
+            expect_len("van_der_pol", "params", params.len(), 1)?;
+            expect_len("van_der_pol", "initial state values", y0.len(), 2)?;
+            let system = VanDerPol::new(params[0]);
+            let initial = Vector2::new(y0[0], y0[1]);
+            Ok(sample_system_at(&system, initial, dt, tspan))
+        }
+        "damped_pendulum" => {
+            expect_len("damped_pendulum", "params", params.len(), 2)?;
+            expect_len("damped_pendulum", "initial state values", y0.len(), 2)?;
+            let system = DampedPendulum::new(params[0], params[1]);
+            let initial = Vector2::new(y0[0], y0[1]);
+            Ok(sample_system_at(&system, initial, dt, tspan))
+        }
+        other => Err(format!("unknown system: {other}")),
+    }
+}
+
+/// Thin wasm-exported wrapper around `solve_frames_impl`; see `solve`'s
+/// wrapper for why the `JsValue` conversion has to happen at this boundary.
+#[wasm_bindgen]
+pub fn solve_frames(
+    system_name: &str,
+    params: &[f64],
+    y0: &[f64],
+    dt: f64,
+    tspan: &[f64],
+) -> Result<Vec<f64>, JsValue> {
+    solve_frames_impl(system_name, params, y0, dt, tspan).map_err(|e| JsValue::from_str(&e))
+}
+
+/// Estimate the Lyapunov spectrum of the Lorenz system.
+///
+/// Returns the exponents sorted in descending order; a positive largest
+/// exponent confirms chaotic behavior.
+#[wasm_bindgen]
+pub fn lyapunov_lorenz(
+    sigma: f64,
+    rho: f64,
+    beta: f64,
+    x0: f64,
+    y0: f64,
+    z0: f64,
+    dt: f64,
+    steps: usize,
+    transient: usize,
+) -> Vec<f64> {
+    let lorenz = Lorenz::new(sigma, rho, beta);
+    let initial = Vector3::new(x0, y0, z0);
+
+    lyapunov_spectrum(
+        |t, y| lorenz.derivative(t, y),
+        initial,
+        dt,
+        steps,
+        transient,
+    )
+}
+
+/// Estimate the Lyapunov spectrum of the Rössler system.
+///
+/// Returns the exponents sorted in descending order; a positive largest
+/// exponent confirms chaotic behavior.
+#[wasm_bindgen]
+pub fn lyapunov_rossler(
+    a: f64,
+    b: f64,
+    c: f64,
+    x0: f64,
+    y0: f64,
+    z0: f64,
+    dt: f64,
+    steps: usize,
+    transient: usize,
+) -> Vec<f64> {
+    let rossler = Rossler::new(a, b, c);
+    let initial = Vector3::new(x0, y0, z0);
+
+    lyapunov_spectrum(
+        |t, y| rossler.derivative(t, y),
+        initial,
+        dt,
+        steps,
+        transient,
+    )
+}
+
+/// Check that a caller-supplied slice has the expected length, producing a
+/// uniform error message for `solve`/`solve_stiff`'s per-system validation.
+fn expect_len(what: &str, label: &str, actual: usize, expected: usize) -> Result<(), String> {
+    if actual != expected {
+        Err(format!("{what} expects {expected} {label}, got {actual}"))
+    } else {
+        Ok(())
+    }
+}
+
+/// Integrate any `DynamicalSystem` with RK4.
+///
+/// Generic over the system, so `solve`'s dispatch actually goes through the
+/// `DynamicalSystem` trait instead of each match arm repeating its own
+/// integrator construction and derivative closure.
+fn integrate_system<S, const N: usize>(
+    system: &S,
+    initial: SVector<f64, N>,
+    dt: f64,
+    steps: usize,
+) -> Vec<f64>
+where
+    S: DynamicalSystem<N>,
+{
+    let integrator = RK4Integrator::<N>::new(dt);
+    integrator.integrate(&|t, y| system.derivative(t, y), initial, steps)
+}
+
+/// Solve a named dynamical system, dispatching by name instead of requiring
+/// a bespoke `solve_*` function per system.
+///
+/// `params` and `y0` are validated against the system's expected parameter
+/// and state-space dimension before integrating. Adding a new system (e.g.
+/// Chua's circuit, Hénon–Heiles, a double pendulum) only requires a new
+/// match arm here plus its `DynamicalSystem` impl, not a new export.
+///
+/// Returns a flat array of state values, one group of `dim` per step.
+fn solve_impl(
+    system_name: &str,
+    params: &[f64],
+    y0: &[f64],
+    dt: f64,
+    steps: usize,
+) -> Result<Vec<f64>, String> {
+    match system_name {
+        "lorenz" => {
+            expect_len("lorenz", "params", params.len(), 3)?;
+            expect_len("lorenz", "initial state values", y0.len(), 3)?;
+            let system = Lorenz::new(params[0], params[1], params[2]);
+            let initial = Vector3::new(y0[0], y0[1], y0[2]);
+            Ok(integrate_system(&system, initial, dt, steps))
+        }
+        "rossler" => {
+            expect_len("rossler", "params", params.len(), 3)?;
+            expect_len("rossler", "initial state values", y0.len(), 3)?;
+            let system = Rossler::new(params[0], params[1], params[2]);
+            let initial = Vector3::new(y0[0], y0[1], y0[2]);
+            Ok(integrate_system(&system, initial, dt, steps))
+        }
+        "van_der_pol" => {
+            expect_len("van_der_pol", "params", params.len(), 1)?;
+            expect_len("van_der_pol", "initial state values", y0.len(), 2)?;
+            let system = VanDerPol::new(params[0]);
+            let initial = Vector2::new(y0[0], y0[1]);
+            Ok(integrate_system(&system, initial, dt, steps))
+        }
+        "damped_pendulum" => {
+            expect_len("damped_pendulum", "params", params.len(), 2)?;
+            expect_len("damped_pendulum", "initial state values", y0.len(), 2)?;
+            let system = DampedPendulum::new(params[0], params[1]);
+            let initial = Vector2::new(y0[0], y0[1]);
+            Ok(integrate_system(&system, initial, dt, steps))
+        }
+        other => Err(format!("unknown system: {other}")),
+    }
+}
+
+/// Thin wasm-exported wrapper around `solve_impl`, converting its native
+/// `Result<_, String>` to `JsValue` only at the wasm boundary — constructing
+/// a `JsValue` panics outside a wasm target, so the error path needs to stay
+/// testable natively via `solve_impl` directly.
+#[wasm_bindgen]
+pub fn solve(
+    system_name: &str,
+    params: &[f64],
+    y0: &[f64],
+    dt: f64,
+    steps: usize,
+) -> Result<Vec<f64>, JsValue> {
+    solve_impl(system_name, params, y0, dt, steps).map_err(|e| JsValue::from_str(&e))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -125,4 +548,117 @@ mod tests {
         // Should have 101 points * 3 coordinates = 303 values
         assert_eq!(result.len(), 303);
     }
+
+    #[test]
+    fn test_solve_damped_pendulum_conservative_uses_verlet() {
+        // gamma = 0 should route through the symplectic integrator, keeping
+        // the output layout identical to the damped (RK4) path.
+        let result = solve_damped_pendulum(0.0, 1.0, 1.0, 0.0, 0.01, 100);
+        assert_eq!(result.len(), 202);
+        assert_eq!(result[0], 1.0);
+        assert_eq!(result[1], 0.0);
+    }
+
+    #[test]
+    fn test_solve_dispatches_by_name() {
+        let result = solve("lorenz", &[10.0, 28.0, 8.0 / 3.0], &[1.0, 1.0, 1.0], 0.01, 100)
+            .expect("lorenz should solve");
+        assert_eq!(result.len(), 303);
+        assert_eq!(&result[0..3], &[1.0, 1.0, 1.0]);
+
+        let result = solve("van_der_pol", &[1.0], &[2.0, 0.0], 0.01, 100)
+            .expect("van_der_pol should solve");
+        assert_eq!(result.len(), 202);
+    }
+
+    #[test]
+    fn test_solve_rejects_wrong_param_count() {
+        // Exercises the error path natively: constructing a JsValue (what
+        // `solve` itself would return) panics outside a wasm target, so this
+        // goes through `solve_impl`'s plain `Result<_, String>` instead.
+        assert!(solve_impl("lorenz", &[10.0, 28.0], &[1.0, 1.0, 1.0], 0.01, 10).is_err());
+    }
+
+    #[test]
+    fn test_solve_rejects_unknown_system() {
+        assert!(solve_impl("chua", &[], &[], 0.01, 10).is_err());
+    }
+
+    #[test]
+    fn test_solve_stiff_dispatches_by_name() {
+        // Large gamma/mu make these stiff for RK4 at this dt; the implicit
+        // Rosenbrock path should still produce a finite, bounded trajectory.
+        let result = solve_stiff("damped_pendulum", &[50.0, 1.0], &[1.0, 0.0], 0.01, 100)
+            .expect("damped_pendulum should solve");
+        assert_eq!(result.len(), 202);
+        assert!(result.iter().all(|v| v.is_finite()));
+    }
+
+    #[test]
+    fn test_solve_stiff_rejects_wrong_param_count() {
+        // See test_solve_rejects_wrong_param_count: goes through solve_stiff_impl
+        // since constructing a JsValue (what solve_stiff returns) panics natively.
+        assert!(solve_stiff_impl("lorenz", &[10.0], &[1.0, 1.0, 1.0], 0.01, 10).is_err());
+    }
+
+    #[test]
+    fn test_solve_adaptive_dispatches_by_name() {
+        let result = solve_adaptive_impl(
+            "van_der_pol",
+            &[1.0],
+            &[2.0, 0.0],
+            1e-6,
+            1e-8,
+            1.0,
+            0.0,
+            5.0,
+        )
+        .expect("van_der_pol should solve");
+
+        // Each accepted step contributes 1 + dim = 3 values.
+        assert!(result.len() % 3 == 0);
+        assert_eq!(&result[0..3], &[0.0, 2.0, 0.0]);
+    }
+
+    #[test]
+    fn test_solve_adaptive_rejects_wrong_param_count() {
+        assert!(solve_adaptive_impl(
+            "lorenz",
+            &[10.0],
+            &[1.0, 1.0, 1.0],
+            1e-6,
+            1e-8,
+            1.0,
+            0.0,
+            1.0,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_solve_frames_dispatches_by_name() {
+        let tspan: Vec<f64> = (0..=10).map(|i| i as f64 * 0.1).collect();
+        let result = solve_frames_impl("van_der_pol", &[1.0], &[2.0, 0.0], 0.01, &tspan)
+            .expect("van_der_pol should solve");
+
+        assert_eq!(result.len(), tspan.len() * 2);
+        assert_eq!(&result[0..2], &[2.0, 0.0]);
+    }
+
+    #[test]
+    fn test_solve_frames_rejects_wrong_param_count() {
+        let tspan = [0.0, 1.0];
+        assert!(solve_frames_impl("lorenz", &[10.0], &[1.0, 1.0, 1.0], 0.01, &tspan).is_err());
+    }
+
+    #[test]
+    fn test_lyapunov_lorenz_is_chaotic() {
+        // Classic Lorenz parameters are known to have a positive largest
+        // Lyapunov exponent (~0.9), confirming chaos.
+        let exponents = lyapunov_lorenz(
+            10.0, 28.0, 8.0 / 3.0, 1.0, 1.0, 1.0, 0.005, 4000, 1000,
+        );
+        assert_eq!(exponents.len(), 3);
+        assert!(exponents[0] > 0.0, "Expected a positive largest exponent, got {:?}", exponents);
+    }
 }
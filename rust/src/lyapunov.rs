@@ -0,0 +1,103 @@
+use nalgebra::SMatrix;
+use nalgebra::SVector;
+
+use crate::ode::{jacobian, RK4Integrator};
+
+/// Estimate the full spectrum of Lyapunov exponents via the Benettin /
+/// Gram-Schmidt method.
+///
+/// Integrates the reference trajectory with `RK4Integrator` while evolving
+/// N orthonormal perturbation vectors under the linearized dynamics (the
+/// Jacobian of `f`, obtained by finite differences). After every step the
+/// perturbation set is re-orthonormalized via Gram-Schmidt, and the log of
+/// each vector's pre-normalization length is accumulated; dividing the sums
+/// by the elapsed time (after discarding `transient` steps to let the
+/// trajectory settle onto the attractor) gives the exponents.
+///
+/// `transient` is clamped below `steps` so at least one step is accumulated
+/// whenever `steps > 0`, since both are caller-supplied and `transient >= steps`
+/// would otherwise leave no elapsed time to divide by.
+///
+/// Returns the exponents sorted in descending order; a positive largest
+/// exponent confirms chaos.
+pub fn lyapunov_spectrum<F, const N: usize>(
+    f: F,
+    y0: SVector<f64, N>,
+    dt: f64,
+    steps: usize,
+    transient: usize,
+) -> Vec<f64>
+where
+    F: Fn(f64, &SVector<f64, N>) -> SVector<f64, N>,
+{
+    let transient = transient.min(steps.saturating_sub(1));
+    let integrator = RK4Integrator::<N>::new(dt);
+    let mut y = y0;
+    let mut t = 0.0;
+    let mut q = SMatrix::<f64, N, N>::identity();
+    let mut sums = [0.0_f64; N];
+
+    for step in 0..steps {
+        let jac = jacobian(&f, t, &y);
+        y = integrator.step(&f, t, &y);
+        t += dt;
+
+        // Evolve each perturbation vector under the linearized dynamics.
+        for col in 0..N {
+            let v = q.column(col).clone_owned();
+            let v_new = v + (jac * v) * dt;
+            q.set_column(col, &v_new);
+        }
+
+        // Gram-Schmidt re-orthonormalization; the accumulated pre-normalization
+        // lengths are what the Lyapunov exponents are built from.
+        let mut lengths = [0.0_f64; N];
+        for (col, length_slot) in lengths.iter_mut().enumerate() {
+            let mut v = q.column(col).clone_owned();
+            for prev in 0..col {
+                let u = q.column(prev).clone_owned();
+                v -= u * v.dot(&u);
+            }
+            let length = v.norm();
+            *length_slot = length;
+            if length > 1e-300 {
+                q.set_column(col, &(v / length));
+            } else {
+                q.set_column(col, &v);
+            }
+        }
+
+        if step >= transient {
+            for (sum, length) in sums.iter_mut().zip(lengths.iter()) {
+                *sum += length.ln();
+            }
+        }
+    }
+
+    let elapsed = (steps - transient) as f64 * dt;
+    let mut exponents: Vec<f64> = (0..N).map(|i| sums[i] / elapsed).collect();
+    exponents.sort_by(|a, b| b.partial_cmp(a).unwrap());
+    exponents
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::Vector1;
+
+    #[test]
+    fn test_transient_at_least_steps_does_not_panic() {
+        // transient >= steps must not underflow the `steps - transient`
+        // subtraction; it should clamp instead of panicking or returning NaN.
+        let f = |_t: f64, y: &Vector1<f64>| Vector1::new(-y[0]);
+        let y0 = Vector1::new(1.0);
+
+        let exponents = lyapunov_spectrum(f, y0, 0.01, 10, 10);
+        assert_eq!(exponents.len(), 1);
+        assert!(exponents[0].is_finite());
+
+        let exponents = lyapunov_spectrum(f, y0, 0.01, 10, 50);
+        assert_eq!(exponents.len(), 1);
+        assert!(exponents[0].is_finite());
+    }
+}
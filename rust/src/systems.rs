@@ -1,4 +1,20 @@
-use nalgebra::{Vector2, Vector3};
+use nalgebra::{SVector, Vector2, Vector3};
+
+/// A dynamical system with a known state-space dimension.
+///
+/// Gives integrators and the wasm dispatch layer a uniform interface across
+/// systems instead of one bespoke `solve_*` function per system. Adding a
+/// new system is then a matter of a struct, an `impl DynamicalSystem<N>`,
+/// and a registry line in `solve`, rather than a new exported function.
+pub trait DynamicalSystem<const N: usize> {
+    /// The state-space dimension (equal to the const generic `N`).
+    fn dim() -> usize {
+        N
+    }
+
+    /// Evaluate dy/dt = f(t, y).
+    fn derivative(&self, t: f64, state: &SVector<f64, N>) -> SVector<f64, N>;
+}
 
 /// Lorenz attractor system.
 ///
@@ -31,6 +47,12 @@ impl Lorenz {
     }
 }
 
+impl DynamicalSystem<3> for Lorenz {
+    fn derivative(&self, t: f64, state: &Vector3<f64>) -> Vector3<f64> {
+        Lorenz::derivative(self, t, state)
+    }
+}
+
 /// Van der Pol oscillator.
 ///
 /// dx/dt = y
@@ -54,6 +76,12 @@ impl VanDerPol {
     }
 }
 
+impl DynamicalSystem<2> for VanDerPol {
+    fn derivative(&self, t: f64, state: &Vector2<f64>) -> Vector2<f64> {
+        VanDerPol::derivative(self, t, state)
+    }
+}
+
 /// Damped pendulum.
 ///
 /// dθ/dt = ω
@@ -79,6 +107,31 @@ impl DampedPendulum {
             -self.gamma * omega - self.omega0 * self.omega0 * theta.sin(),
         )
     }
+
+    /// Whether this pendulum is Hamiltonian (no damping), and so can be
+    /// integrated with a symplectic scheme like `VelocityVerletIntegrator`
+    /// without secular energy drift.
+    pub fn is_conservative(&self) -> bool {
+        self.gamma == 0.0
+    }
+
+    /// Angular acceleration θ̈ = -ω₀²sin(θ), used by
+    /// `VelocityVerletIntegrator` when `gamma == 0`.
+    pub fn acceleration(&self, theta: f64) -> f64 {
+        -self.omega0 * self.omega0 * theta.sin()
+    }
+
+    /// Total mechanical energy (kinetic + potential, up to the arbitrary
+    /// additive constant `ω₀²`) for a unit-mass, unit-length pendulum.
+    pub fn energy(&self, theta: f64, omega: f64) -> f64 {
+        0.5 * omega * omega + self.omega0 * self.omega0 * (1.0 - theta.cos())
+    }
+}
+
+impl DynamicalSystem<2> for DampedPendulum {
+    fn derivative(&self, t: f64, state: &Vector2<f64>) -> Vector2<f64> {
+        DampedPendulum::derivative(self, t, state)
+    }
 }
 
 /// Rössler system.
@@ -108,6 +161,12 @@ impl Rossler {
     }
 }
 
+impl DynamicalSystem<3> for Rossler {
+    fn derivative(&self, t: f64, state: &Vector3<f64>) -> Vector3<f64> {
+        Rossler::derivative(self, t, state)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -163,4 +222,23 @@ mod tests {
         assert!((deriv[1] - 1.2).abs() < 1e-10);
         assert!((deriv[2] - (-4.5)).abs() < 1e-10);
     }
+
+    #[test]
+    fn test_dynamical_system_dims() {
+        assert_eq!(<Lorenz as DynamicalSystem<3>>::dim(), 3);
+        assert_eq!(<Rossler as DynamicalSystem<3>>::dim(), 3);
+        assert_eq!(<VanDerPol as DynamicalSystem<2>>::dim(), 2);
+        assert_eq!(<DampedPendulum as DynamicalSystem<2>>::dim(), 2);
+    }
+
+    #[test]
+    fn test_dynamical_system_matches_inherent_derivative() {
+        let lorenz = Lorenz::new(10.0, 28.0, 8.0 / 3.0);
+        let state = Vector3::new(1.0, 1.0, 1.0);
+
+        let via_trait = DynamicalSystem::derivative(&lorenz, 0.0, &state);
+        let via_inherent = lorenz.derivative(0.0, &state);
+
+        assert_eq!(via_trait, via_inherent);
+    }
 }